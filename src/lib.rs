@@ -15,14 +15,30 @@ pub trait GpioOut {
     }
 
     /// Set the GPIO port to a low output value directly
-    #[inline(always)]
     fn set_low(&mut self) -> Result<(), Self::Error>;
 
     /// Set the GPIO port to a high output value directly
-    #[inline(always)]
     fn set_high(&mut self) -> Result<(), Self::Error>;
 }
 
+/// Blanket adapter letting any [`embedded_hal::digital::OutputPin`] be used
+/// directly as a [`GpioOut`], so HAL pins can be handed straight to
+/// [`TlcController::new`] without a hand-written shim.
+#[cfg(feature = "embedded-hal")]
+impl<T: embedded_hal::digital::OutputPin> GpioOut for T {
+    type Error = <T as embedded_hal::digital::ErrorType>::Error;
+
+    #[inline(always)]
+    fn set_low(&mut self) -> Result<(), Self::Error> {
+        embedded_hal::digital::OutputPin::set_low(self)
+    }
+
+    #[inline(always)]
+    fn set_high(&mut self) -> Result<(), Self::Error> {
+        embedded_hal::digital::OutputPin::set_high(self)
+    }
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub enum GpioValue {
     /// A low value, usually 0 V
@@ -40,29 +56,121 @@ trait GpioOutExt: GpioOut {
 
 impl<T: GpioOut> GpioOutExt for T {}
 
-pub struct TlcController<Pin> {
-    sin: Pin,
-    sclk: Pin,
-    blank: Pin,
-    xlat: Pin,
-    gsclk: Pin,
-    values: [u16; 16],
+/// Supports reading the logic level of a GPIO input.
+pub trait GpioIn {
+    /// Errors that can occur while reading the GPIO input.
+    type Error;
+
+    /// Returns `true` if the input is at a high level.
+    fn is_high(&mut self) -> Result<bool, Self::Error>;
+
+    /// Returns `true` if the input is at a low level.
+    fn is_low(&mut self) -> Result<bool, Self::Error>;
 }
 
-impl<Pin, Error> TlcController<Pin>
+/// Blanket adapter letting any [`embedded_hal::digital::InputPin`] be used as
+/// the XERR input, mirroring the [`GpioOut`] adapter.
+#[cfg(feature = "embedded-hal")]
+impl<T: embedded_hal::digital::InputPin> GpioIn for T {
+    type Error = <T as embedded_hal::digital::ErrorType>::Error;
+
+    #[inline(always)]
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        embedded_hal::digital::InputPin::is_high(self)
+    }
+
+    #[inline(always)]
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        embedded_hal::digital::InputPin::is_low(self)
+    }
+}
+
+/// No-op [`GpioIn`] used as the default XERR type when no error pin is wired;
+/// it always reports the idle (no-fault) level.
+///
+/// A dedicated type is used rather than `()` so the blanket
+/// [`embedded_hal::digital::InputPin`] adapter above cannot collide with it
+/// under coherence.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub struct NoErrorPin;
+
+impl GpioIn for NoErrorPin {
+    type Error = core::convert::Infallible;
+
+    #[inline(always)]
+    fn is_high(&mut self) -> Result<bool, Self::Error> {
+        Ok(true)
+    }
+
+    #[inline(always)]
+    fn is_low(&mut self) -> Result<bool, Self::Error> {
+        Ok(false)
+    }
+}
+
+/// Fault flags sampled from the open-drain XERR line.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Default)]
+pub struct ErrorFlags {
+    /// `true` when XERR is asserted (low), signalling an open LED channel or a
+    /// thermal-shutdown condition on at least one TLC5940 in the chain.
+    pub fault: bool,
+}
+
+pub struct TlcController<Sin, Sclk, Blank, Xlat, Gsclk, Xerr = NoErrorPin, const CHIPS: usize = 1> {
+    sin: Sin,
+    sclk: Sclk,
+    blank: Blank,
+    xlat: Xlat,
+    gsclk: Gsclk,
+    xerr: Xerr,
+    values: [[u16; 16]; CHIPS],
+    error_flags: ErrorFlags,
+}
+
+impl<Sin, Sclk, Blank, Xlat, Gsclk, Error, const CHIPS: usize>
+    TlcController<Sin, Sclk, Blank, Xlat, Gsclk, NoErrorPin, CHIPS>
 where
-    Pin: GpioOut<Error = Error>,
+    Sin: GpioOut<Error = Error>,
+    Sclk: GpioOut<Error = Error>,
+    Blank: GpioOut<Error = Error>,
+    Xlat: GpioOut<Error = Error>,
+    Gsclk: GpioOut<Error = Error>,
 {
     pub fn new(
-        mut sin: Pin,
-        mut sclk: Pin,
-        mut blank: Pin,
-        mut xlat: Pin,
-        mut gsclk: Pin,
+        sin: Sin,
+        sclk: Sclk,
+        blank: Blank,
+        xlat: Xlat,
+        gsclk: Gsclk,
     ) -> Result<Self, Error> {
-        [&mut sin, &mut sclk, &mut xlat, &mut gsclk]
-            .into_iter()
-            .try_for_each(GpioOut::set_low)?;
+        Self::new_with_error_input(sin, sclk, blank, xlat, gsclk, NoErrorPin)
+    }
+}
+
+impl<Sin, Sclk, Blank, Xlat, Gsclk, Xerr, Error, const CHIPS: usize>
+    TlcController<Sin, Sclk, Blank, Xlat, Gsclk, Xerr, CHIPS>
+where
+    Sin: GpioOut<Error = Error>,
+    Sclk: GpioOut<Error = Error>,
+    Blank: GpioOut<Error = Error>,
+    Xlat: GpioOut<Error = Error>,
+    Gsclk: GpioOut<Error = Error>,
+    Xerr: GpioIn,
+{
+    /// Like [`new`](Self::new), but also wires an XERR error-flag input so
+    /// faults can be read back with [`error_status`](Self::error_status).
+    pub fn new_with_error_input(
+        mut sin: Sin,
+        mut sclk: Sclk,
+        mut blank: Blank,
+        mut xlat: Xlat,
+        mut gsclk: Gsclk,
+        xerr: Xerr,
+    ) -> Result<Self, Error> {
+        sin.set_low()?;
+        sclk.set_low()?;
+        xlat.set_low()?;
+        gsclk.set_low()?;
         blank.set_high()?;
         Ok(Self {
             sin,
@@ -70,16 +178,27 @@ where
             blank,
             xlat,
             gsclk,
-            values: core::array::from_fn(|_| 0),
+            xerr,
+            values: [[0; 16]; CHIPS],
+            error_flags: ErrorFlags { fault: false },
         })
     }
 
     pub fn set_channel(&mut self, channel: usize, color: u16) {
-        self.values[channel] = color;
+        self.values[channel / 16][channel % 16] = color;
+    }
+
+    /// Addresses a channel by its position within a specific chip in the chain,
+    /// where `chip` 0 is the one nearest the MCU (the first to receive data is
+    /// the one farthest down the chain, addressed by the highest `chip` index).
+    pub fn set_channel_on_chip(&mut self, chip: usize, channel: usize, color: u16) {
+        self.values[chip][channel] = color;
     }
 
     pub fn set_all(&mut self, value: u16) {
-        self.values.iter_mut().for_each(|num| *num = value);
+        self.values
+            .iter_mut()
+            .for_each(|chip| chip.iter_mut().for_each(|num| *num = value));
     }
 
     pub fn clear(&mut self) {
@@ -88,24 +207,31 @@ where
 
     pub fn update(&mut self) -> Result<(), Error> {
         self.update_init()?;
-        let mut channel_counter = (self.values.len() - 1) as isize;
-        let mut gsclk_counter = 0;
-        while gsclk_counter < 4096 {
-            if channel_counter >= 0 {
-                for i in (0..12).rev() {
-                    let val = self.get_pin_value_for_channel(channel_counter as usize, i);
-                    self.sin.set_value(val)?;
-                    self.sclk.pulse()?;
+        let channels = 16 * CHIPS;
+        let mut gsclk_counter = 0usize;
+        // Shift every data bit out most-significant-chip-first (the first bits
+        // clocked out land in the chip farthest down the chain). GSCLK is pulsed
+        // alongside the shift but never past the 4096 cycles one grayscale period
+        // needs, so a chain longer than 4096/12 channels still receives every
+        // bit instead of having its tail silently dropped.
+        for channel in (0..channels).rev() {
+            for bit in (0..12).rev() {
+                let val = self.get_pin_value_for_channel(channel, bit);
+                self.sin.set_value(val)?;
+                self.sclk.pulse()?;
+                if gsclk_counter < 4096 {
                     self.gsclk.pulse()?;
                     gsclk_counter += 1;
                 }
-                channel_counter -= 1;
-            } else {
-                self.sin.set_low()?;
-                self.gsclk.pulse()?;
-                gsclk_counter += 1
             }
         }
+        self.sin.set_low()?;
+        // Top up GSCLK so the currently latched frame still gets a full
+        // 4096-cycle grayscale period when the shift was shorter than that.
+        while gsclk_counter < 4096 {
+            self.gsclk.pulse()?;
+            gsclk_counter += 1;
+        }
         self.update_post()
     }
 
@@ -116,13 +242,610 @@ where
     fn update_post(&mut self) -> Result<(), Error> {
         self.blank.set_high()?;
         self.xlat.pulse()?;
+        // XERR is valid once the new grayscale data has been latched, so sample
+        // it here; a read error just leaves the previously latched flags intact.
+        let _ = self.error_status();
         Ok(())
     }
 
     fn get_pin_value_for_channel(&self, channel: usize, bit: u8) -> GpioValue {
-        match (self.values[channel] & (1 << bit)) >> bit == 0 {
+        let value = self.values[channel / 16][channel % 16];
+        match (value & (1 << bit)) >> bit == 0 {
             true => GpioValue::Low,
             false => GpioValue::High,
         }
     }
+
+    /// Samples the open-drain XERR line and latches the result. XERR is
+    /// active-low, so a low reading means a fault is present. [`update`] calls
+    /// this right after pulsing XLAT; [`latched_error_status`] returns the last
+    /// value without re-reading the pin.
+    ///
+    /// [`update`]: Self::update
+    /// [`latched_error_status`]: Self::latched_error_status
+    pub fn error_status(&mut self) -> Result<ErrorFlags, Xerr::Error> {
+        let fault = self.xerr.is_low()?;
+        self.error_flags = ErrorFlags { fault };
+        Ok(self.error_flags)
+    }
+
+    /// Returns the error flags from the most recent successful XERR sample,
+    /// whether taken by [`update`] or by a direct [`error_status`] call.
+    ///
+    /// [`update`]: Self::update
+    /// [`error_status`]: Self::error_status
+    pub fn latched_error_status(&self) -> ErrorFlags {
+        self.error_flags
+    }
+}
+
+/// Number of bytes in a packed 16-channel, 12-bit grayscale frame.
+#[cfg(feature = "embedded-hal")]
+const FRAME_BYTES: usize = 16 * 12 / 8;
+
+/// Error raised by the SPI-backed controller, distinguishing SPI-bus failures
+/// from GPIO failures on the BLANK/XLAT pins.
+#[cfg(feature = "embedded-hal")]
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SpiError<Spi, Pin> {
+    /// A transfer on the grayscale SPI bus failed.
+    Spi(Spi),
+    /// Driving the BLANK or XLAT pin failed.
+    Pin(Pin),
+}
+
+/// A TLC5940 controller that offloads the grayscale serial shift to a hardware
+/// [`SpiBus`](embedded_hal::spi::SpiBus) and the GSCLK to a free-running
+/// hardware PWM/timer output.
+///
+/// Unlike [`TlcController`], which bit-bangs every SIN/SCLK/GSCLK edge,
+/// [`update`](Self::update) only packs the buffer into 24 bytes, writes them in
+/// a single `write()`, and pulses XLAT inside a brief BLANK-high pulse. GSCLK is assumed
+/// to be clocked independently by the PWM peripheral, so it never appears here.
+#[cfg(feature = "embedded-hal")]
+pub struct SpiTlcController<Spi, Blank, Xlat> {
+    spi: Spi,
+    blank: Blank,
+    xlat: Xlat,
+    values: [u16; 16],
+}
+
+#[cfg(feature = "embedded-hal")]
+impl<Spi, Blank, Xlat, PinError> SpiTlcController<Spi, Blank, Xlat>
+where
+    Spi: embedded_hal::spi::SpiBus<u8>,
+    Blank: GpioOut<Error = PinError>,
+    Xlat: GpioOut<Error = PinError>,
+{
+    pub fn new(
+        spi: Spi,
+        mut blank: Blank,
+        mut xlat: Xlat,
+    ) -> Result<Self, SpiError<Spi::Error, PinError>> {
+        xlat.set_low().map_err(SpiError::Pin)?;
+        blank.set_high().map_err(SpiError::Pin)?;
+        Ok(Self {
+            spi,
+            blank,
+            xlat,
+            values: core::array::from_fn(|_| 0),
+        })
+    }
+
+    pub fn set_channel(&mut self, channel: usize, color: u16) {
+        self.values[channel] = color;
+    }
+
+    pub fn set_all(&mut self, value: u16) {
+        self.values.iter_mut().for_each(|num| *num = value);
+    }
+
+    pub fn clear(&mut self) {
+        self.set_all(0);
+    }
+
+    pub fn update(&mut self) -> Result<(), SpiError<Spi::Error, PinError>> {
+        let frame = self.pack();
+        // The external GSCLK runs continuously, so `update` must leave the
+        // outputs enabled (BLANK low) for the rest of the grayscale period.
+        // Shift the next frame in, latch it during a brief BLANK-high pulse so
+        // the change is not seen mid-period, then drop BLANK again to light it.
+        self.spi.write(&frame).map_err(SpiError::Spi)?;
+        self.spi.flush().map_err(SpiError::Spi)?;
+        self.blank.set_high().map_err(SpiError::Pin)?;
+        self.xlat.pulse().map_err(SpiError::Pin)?;
+        self.blank.set_low().map_err(SpiError::Pin)?;
+        Ok(())
+    }
+
+    /// Packs the grayscale buffer into a byte frame, most-significant channel
+    /// first with each 12-bit value shifted out MSB-first, matching the order
+    /// the TLC5940 expects on SIN.
+    fn pack(&self) -> [u8; FRAME_BYTES] {
+        let mut frame = [0u8; FRAME_BYTES];
+        let mut bit_index = 0;
+        for channel in (0..self.values.len()).rev() {
+            let value = self.values[channel] & 0x0fff;
+            for bit in (0..12).rev() {
+                if (value >> bit) & 1 != 0 {
+                    frame[bit_index / 8] |= 0x80 >> (bit_index % 8);
+                }
+                bit_index += 1;
+            }
+        }
+        frame
+    }
+}
+
+/// Continuous-refresh driver for the SPI/PWM backend.
+///
+/// The hardware GSCLK/PWM output clocks the 4096-cycle grayscale periods on its
+/// own, so the application only has to re-latch a fresh frame on each period
+/// boundary. [`poll`](Self::poll) performs exactly one such step and is meant to
+/// be called once per grayscale period — from a PWM-wrap interrupt, a timer
+/// task, or an `async` loop that yields between calls.
+///
+/// Channel writes land in a back buffer and are promoted to the displayed
+/// buffer only on the next boundary, so another task can edit channels without
+/// tearing the frame currently being shifted out.
+#[cfg(feature = "embedded-hal")]
+pub struct RefreshDriver<Spi, Blank, Xlat> {
+    controller: SpiTlcController<Spi, Blank, Xlat>,
+    pending: [u16; 16],
+    dirty: bool,
+}
+
+#[cfg(feature = "embedded-hal")]
+impl<Spi, Blank, Xlat, PinError> RefreshDriver<Spi, Blank, Xlat>
+where
+    Spi: embedded_hal::spi::SpiBus<u8>,
+    Blank: GpioOut<Error = PinError>,
+    Xlat: GpioOut<Error = PinError>,
+{
+    pub fn new(controller: SpiTlcController<Spi, Blank, Xlat>) -> Self {
+        let pending = controller.values;
+        Self {
+            controller,
+            pending,
+            dirty: false,
+        }
+    }
+
+    pub fn set_channel(&mut self, channel: usize, color: u16) {
+        self.pending[channel] = color;
+        self.dirty = true;
+    }
+
+    pub fn set_all(&mut self, value: u16) {
+        self.pending.iter_mut().for_each(|num| *num = value);
+        self.dirty = true;
+    }
+
+    pub fn clear(&mut self) {
+        self.set_all(0);
+    }
+
+    /// Advances the refresh by one grayscale period: promotes the back buffer
+    /// if it changed since the last boundary, then re-latches the frame.
+    pub fn poll(&mut self) -> Result<(), SpiError<Spi::Error, PinError>> {
+        if self.dirty {
+            self.controller.values = self.pending;
+            self.dirty = false;
+        }
+        self.controller.update()
+    }
+
+    /// Drives the display forever, yielding back to the executor between
+    /// grayscale periods via `wait`. `wait` should resolve once per period
+    /// (typically on the PWM-wrap event), mirroring the embassy GPIOTE model.
+    ///
+    /// Because the loop never returns there is no channel to report a per-period
+    /// SPI/pin failure on, so the [`poll`](Self::poll) result is intentionally
+    /// discarded and the refresh keeps running. Callers that need fault feedback
+    /// should either drive [`poll`](Self::poll) themselves and inspect its
+    /// `Result`, or wire the TLC5940 XERR line through [`TlcController`] and read
+    /// it back with [`error_status`](TlcController::error_status).
+    pub async fn run<Wait, Fut>(mut self, mut wait: Wait) -> !
+    where
+        Wait: FnMut() -> Fut,
+        Fut: core::future::Future<Output = ()>,
+    {
+        loop {
+            // See the method docs: a `-> !` loop has no way to surface this.
+            let _ = self.poll();
+            wait().await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod cascade_tests {
+    extern crate std;
+
+    use super::*;
+    use core::convert::Infallible;
+    use std::{cell::RefCell, rc::Rc, vec::Vec};
+
+    /// Shared state recording the SIN level at each SCLK rising edge.
+    #[derive(Default)]
+    struct ShiftLog {
+        sin: bool,
+        bits: Vec<bool>,
+        gsclk_pulses: usize,
+    }
+
+    /// SIN mock: just tracks the level the next SCLK edge will sample.
+    struct SinPin(Rc<RefCell<ShiftLog>>);
+    impl GpioOut for SinPin {
+        type Error = Infallible;
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.0.borrow_mut().sin = false;
+            Ok(())
+        }
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.0.borrow_mut().sin = true;
+            Ok(())
+        }
+    }
+
+    /// SCLK mock: records the current SIN level on every rising edge.
+    struct SclkPin(Rc<RefCell<ShiftLog>>);
+    impl GpioOut for SclkPin {
+        type Error = Infallible;
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            let mut log = self.0.borrow_mut();
+            let bit = log.sin;
+            log.bits.push(bit);
+            Ok(())
+        }
+    }
+
+    /// GSCLK mock: counts grayscale pulses so we can assert the 4096 budget.
+    struct GsclkPin(Rc<RefCell<ShiftLog>>);
+    impl GpioOut for GsclkPin {
+        type Error = Infallible;
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.0.borrow_mut().gsclk_pulses += 1;
+            Ok(())
+        }
+    }
+
+    /// BLANK/XLAT mock: does nothing but satisfy the `GpioOut` bound.
+    struct NullPin;
+    impl GpioOut for NullPin {
+        type Error = Infallible;
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    /// XERR mock reporting a fixed level; `fault` drives the active-low line low.
+    struct XerrPin {
+        fault: bool,
+    }
+    impl GpioIn for XerrPin {
+        type Error = Infallible;
+        fn is_high(&mut self) -> Result<bool, Self::Error> {
+            Ok(!self.fault)
+        }
+        fn is_low(&mut self) -> Result<bool, Self::Error> {
+            Ok(self.fault)
+        }
+    }
+
+    /// XERR mock whose reads always fail, exercising the swallow-on-error path.
+    struct ErringXerr;
+    impl GpioIn for ErringXerr {
+        type Error = ();
+        fn is_high(&mut self) -> Result<bool, Self::Error> {
+            Err(())
+        }
+        fn is_low(&mut self) -> Result<bool, Self::Error> {
+            Err(())
+        }
+    }
+
+    fn controller<const CHIPS: usize>(
+        log: &Rc<RefCell<ShiftLog>>,
+    ) -> TlcController<SinPin, SclkPin, NullPin, NullPin, GsclkPin, NoErrorPin, CHIPS> {
+        TlcController::new(
+            SinPin(log.clone()),
+            SclkPin(log.clone()),
+            NullPin,
+            NullPin,
+            GsclkPin(log.clone()),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn flat_and_chip_addressing_agree() {
+        let log_a = Rc::new(RefCell::new(ShiftLog::default()));
+        let mut flat = controller::<2>(&log_a);
+        flat.set_channel(31, 0x0abc);
+        flat.set_channel(16, 0x0123);
+        flat.update().unwrap();
+
+        let log_b = Rc::new(RefCell::new(ShiftLog::default()));
+        let mut chip = controller::<2>(&log_b);
+        chip.set_channel_on_chip(1, 15, 0x0abc);
+        chip.set_channel_on_chip(1, 0, 0x0123);
+        chip.update().unwrap();
+
+        assert_eq!(log_a.borrow().bits, log_b.borrow().bits);
+    }
+
+    #[test]
+    fn shifts_most_significant_chip_first() {
+        let log = Rc::new(RefCell::new(ShiftLog::default()));
+        let mut tlc = controller::<2>(&log);
+        // Channel 15 of the farthest chip is the very first one clocked out.
+        tlc.set_channel_on_chip(1, 15, 0x0fff);
+        tlc.update().unwrap();
+
+        let log = log.borrow();
+        assert_eq!(log.bits.len(), 16 * 2 * 12);
+        assert!(log.bits[0..12].iter().all(|&b| b));
+        assert_eq!(log.bits.iter().filter(|&&b| b).count(), 12);
+    }
+
+    #[test]
+    fn long_chain_is_not_truncated() {
+        // 22 chips => 22*16*12 = 4224 data bits, more than the 4096 GSCLK
+        // pulses a grayscale period needs. Every bit must still be clocked.
+        let log = Rc::new(RefCell::new(ShiftLog::default()));
+        let mut tlc = controller::<22>(&log);
+        tlc.update().unwrap();
+
+        let log = log.borrow();
+        assert_eq!(log.bits.len(), 16 * 22 * 12);
+        assert_eq!(log.gsclk_pulses, 4096);
+    }
+
+    #[test]
+    fn short_chain_still_gets_full_grayscale_period() {
+        let log = Rc::new(RefCell::new(ShiftLog::default()));
+        let mut tlc = controller::<1>(&log);
+        tlc.update().unwrap();
+
+        let log = log.borrow();
+        assert_eq!(log.bits.len(), 16 * 12);
+        assert_eq!(log.gsclk_pulses, 4096);
+    }
+
+    fn controller_with_xerr<Xerr: GpioIn>(
+        log: &Rc<RefCell<ShiftLog>>,
+        xerr: Xerr,
+    ) -> TlcController<SinPin, SclkPin, NullPin, NullPin, GsclkPin, Xerr, 1> {
+        TlcController::new_with_error_input(
+            SinPin(log.clone()),
+            SclkPin(log.clone()),
+            NullPin,
+            NullPin,
+            GsclkPin(log.clone()),
+            xerr,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn update_latches_xerr_fault() {
+        let log = Rc::new(RefCell::new(ShiftLog::default()));
+        let mut tlc = controller_with_xerr(&log, XerrPin { fault: true });
+        // `update` samples XERR after XLAT; an asserted (low) line is a fault.
+        tlc.update().unwrap();
+        assert_eq!(tlc.latched_error_status(), ErrorFlags { fault: true });
+        assert_eq!(tlc.error_status().unwrap(), ErrorFlags { fault: true });
+    }
+
+    #[test]
+    fn update_reports_no_fault_when_xerr_idle() {
+        let log = Rc::new(RefCell::new(ShiftLog::default()));
+        let mut tlc = controller_with_xerr(&log, XerrPin { fault: false });
+        tlc.update().unwrap();
+        assert_eq!(tlc.latched_error_status(), ErrorFlags { fault: false });
+    }
+
+    #[test]
+    fn update_swallows_xerr_read_errors() {
+        let log = Rc::new(RefCell::new(ShiftLog::default()));
+        let mut tlc = controller_with_xerr(&log, ErringXerr);
+        // A failed XERR read must not fail the frame; flags stay at their default.
+        tlc.update().unwrap();
+        assert_eq!(tlc.latched_error_status(), ErrorFlags { fault: false });
+    }
+}
+
+#[cfg(all(test, feature = "embedded-hal"))]
+mod spi_tests {
+    extern crate std;
+
+    use super::*;
+    use core::convert::Infallible;
+    use std::{cell::RefCell, rc::Rc, vec::Vec};
+
+    /// SPI mock recording every byte handed to `write()`.
+    pub(crate) struct MockSpi(pub Rc<RefCell<Vec<u8>>>);
+
+    impl embedded_hal::spi::ErrorType for MockSpi {
+        type Error = Infallible;
+    }
+
+    impl embedded_hal::spi::SpiBus<u8> for MockSpi {
+        fn read(&mut self, _words: &mut [u8]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+        fn write(&mut self, words: &[u8]) -> Result<(), Self::Error> {
+            self.0.borrow_mut().extend_from_slice(words);
+            Ok(())
+        }
+        fn transfer(&mut self, _read: &mut [u8], _write: &[u8]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+        fn transfer_in_place(&mut self, _words: &mut [u8]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+        fn flush(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    /// BLANK/XLAT mock satisfying the `GpioOut` bound.
+    pub(crate) struct NullPin;
+    impl GpioOut for NullPin {
+        type Error = Infallible;
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    /// BLANK mock recording every level transition (`true` = high/blanked).
+    pub(crate) struct RecordingPin(pub Rc<RefCell<Vec<bool>>>);
+    impl GpioOut for RecordingPin {
+        type Error = Infallible;
+        fn set_low(&mut self) -> Result<(), Self::Error> {
+            self.0.borrow_mut().push(false);
+            Ok(())
+        }
+        fn set_high(&mut self) -> Result<(), Self::Error> {
+            self.0.borrow_mut().push(true);
+            Ok(())
+        }
+    }
+
+    pub(crate) fn controller(
+        bytes: &Rc<RefCell<Vec<u8>>>,
+    ) -> SpiTlcController<MockSpi, NullPin, NullPin> {
+        SpiTlcController::new(MockSpi(bytes.clone()), NullPin, NullPin).unwrap()
+    }
+
+    #[test]
+    fn update_rests_with_outputs_enabled() {
+        let bytes = Rc::new(RefCell::new(Vec::new()));
+        let blank = Rc::new(RefCell::new(Vec::new()));
+        let mut tlc =
+            SpiTlcController::new(MockSpi(bytes.clone()), RecordingPin(blank.clone()), NullPin)
+                .unwrap();
+        blank.borrow_mut().clear(); // drop the initial blank-high from `new`
+        tlc.update().unwrap();
+
+        let levels = blank.borrow();
+        // The frame is latched during a brief blank-high pulse, then BLANK is
+        // dropped so the free-running GSCLK lights it for the rest of the period.
+        assert!(levels.iter().any(|&high| high));
+        assert_eq!(levels.last(), Some(&false));
+    }
+
+    #[test]
+    fn pack_is_channel_15_first_msb_first() {
+        let bytes = Rc::new(RefCell::new(Vec::new()));
+        let mut tlc = controller(&bytes);
+        // Channel 15 is shifted out first, so its 12 MSB-first bits lead.
+        tlc.set_channel(15, 0x0fff);
+        tlc.update().unwrap();
+
+        let frame = bytes.borrow();
+        assert_eq!(frame.len(), 24);
+        assert_eq!(frame[0], 0xff);
+        assert_eq!(frame[1], 0xf0);
+        assert!(frame[2..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn pack_places_channel_0_in_the_final_bytes() {
+        let bytes = Rc::new(RefCell::new(Vec::new()));
+        let mut tlc = controller(&bytes);
+        // Channel 0 is shifted out last and lands in the last 12 bits.
+        tlc.set_channel(0, 0x0fff);
+        tlc.update().unwrap();
+
+        let frame = bytes.borrow();
+        assert_eq!(frame[23], 0xff);
+        assert_eq!(frame[22], 0x0f);
+        assert!(frame[..22].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn pack_masks_to_12_bits() {
+        let bytes = Rc::new(RefCell::new(Vec::new()));
+        let mut tlc = controller(&bytes);
+        // Bits above the low 12 must be dropped, not bleed into the next channel.
+        tlc.set_channel(15, 0xf000);
+        tlc.update().unwrap();
+
+        assert!(bytes.borrow().iter().all(|&b| b == 0));
+    }
+}
+
+#[cfg(all(test, feature = "embedded-hal"))]
+mod refresh_tests {
+    extern crate std;
+
+    use super::spi_tests::{controller, MockSpi, NullPin, RecordingPin};
+    use super::*;
+    use std::{cell::RefCell, rc::Rc, vec::Vec};
+
+    fn driver(bytes: &Rc<RefCell<Vec<u8>>>) -> RefreshDriver<MockSpi, NullPin, NullPin> {
+        RefreshDriver::new(controller(bytes))
+    }
+
+    #[test]
+    fn writes_are_buffered_until_poll() {
+        let bytes = Rc::new(RefCell::new(Vec::new()));
+        let mut driver = driver(&bytes);
+        driver.set_channel(3, 0x0abc);
+        // The pending edit must not reach the displayed frame before a boundary.
+        assert!(driver.dirty);
+        assert_eq!(driver.controller.values[3], 0);
+        assert!(bytes.borrow().is_empty());
+    }
+
+    #[test]
+    fn poll_promotes_the_back_buffer_and_latches() {
+        let bytes = Rc::new(RefCell::new(Vec::new()));
+        let mut driver = driver(&bytes);
+        driver.set_channel(3, 0x0abc);
+        driver.poll().unwrap();
+
+        assert!(!driver.dirty);
+        assert_eq!(driver.controller.values[3], 0x0abc);
+        assert_eq!(bytes.borrow().len(), 24);
+    }
+
+    #[test]
+    fn clean_poll_still_refreshes() {
+        let bytes = Rc::new(RefCell::new(Vec::new()));
+        let mut driver = driver(&bytes);
+        driver.poll().unwrap();
+        // A full frame is re-latched every period even with no pending edit.
+        assert_eq!(bytes.borrow().len(), 24);
+    }
+
+    #[test]
+    fn poll_leaves_display_enabled() {
+        let bytes = Rc::new(RefCell::new(Vec::new()));
+        let blank = Rc::new(RefCell::new(Vec::new()));
+        let mut driver = RefreshDriver::new(
+            SpiTlcController::new(MockSpi(bytes.clone()), RecordingPin(blank.clone()), NullPin)
+                .unwrap(),
+        );
+        blank.borrow_mut().clear(); // drop the initial blank-high from `new`
+        driver.poll().unwrap();
+
+        // Between refreshes BLANK must rest low, otherwise the panel is dark for
+        // the whole `wait()` period and flickers instead of holding the frame.
+        assert_eq!(blank.borrow().last(), Some(&false));
+    }
 }